@@ -20,13 +20,15 @@ SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 use libc;
 use libc::FILE;
 
-use error::Error;
+use crate::error::Error;
 
-use std::io::SeekFrom;
+use std::io::{self, IoSlice, IoSliceMut, SeekFrom};
 use std::path::Path;
 use std::ffi::CString;
 use std::ptr::null_mut;
 use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A utility function to pull the current value of errno and put it into an Error::Errno
 unsafe fn get_error() -> Error {
@@ -71,6 +73,267 @@ pub static APPEND_READ: &'static str = "a+";
 pub static TRUNCATAE_RANDOM_ACCESS_MODE: &'static str = "wb+";
 
 
+/// A builder for opening a CFile with a declarative set of options instead of a raw fopen
+/// mode string. Set the flags that describe the access you want, then call open() to
+/// resolve them to the matching mode string and perform the open.
+#[derive(Clone, Debug, Default)]
+pub struct CFileOptions {
+    read: bool,
+    write: bool,
+    append: bool,
+    truncate: bool,
+    create: bool,
+    create_new: bool,
+    binary: bool,
+}
+
+impl CFileOptions {
+    /// Creates a blank set of options, all of which start out false.
+    pub fn new() -> CFileOptions {
+        CFileOptions {
+            read: false,
+            write: false,
+            append: false,
+            truncate: false,
+            create: false,
+            create_new: false,
+            binary: false,
+        }
+    }
+
+    /// Sets the option for read access.
+    pub fn read(mut self, read: bool) -> CFileOptions {
+        self.read = read;
+        self
+    }
+
+    /// Sets the option for write access.
+    pub fn write(mut self, write: bool) -> CFileOptions {
+        self.write = write;
+        self
+    }
+
+    /// Sets the option for appending to the end of the file rather than overwriting
+    /// existing content.
+    pub fn append(mut self, append: bool) -> CFileOptions {
+        self.append = append;
+        self
+    }
+
+    /// Sets the option for truncating the file to zero length when opened.
+    pub fn truncate(mut self, truncate: bool) -> CFileOptions {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Sets the option to create the file if it does not exist.
+    pub fn create(mut self, create: bool) -> CFileOptions {
+        self.create = create;
+        self
+    }
+
+    /// Sets the option to create a new file, failing with Error::AlreadyExists if the path
+    /// already exists. Since fopen has no O_EXCL equivalent, this is enforced by checking
+    /// for the path's existence before opening.
+    pub fn create_new(mut self, create_new: bool) -> CFileOptions {
+        self.create_new = create_new;
+        self
+    }
+
+    /// Sets whether the `b` (binary) suffix is appended to the resolved fopen mode string.
+    pub fn binary(mut self, binary: bool) -> CFileOptions {
+        self.binary = binary;
+        self
+    }
+
+    /// Maps the current combination of flags onto the fopen mode string that expresses
+    /// them, or Error::BadPath if the combination can't be expressed by fopen (e.g.
+    /// truncate without write, or a non-truncating write-only open: fopen has no mode
+    /// that writes to an existing file without either truncating it or also granting
+    /// read access, so that combination is rejected rather than silently truncating).
+    fn mode_string(&self) -> Result<String, Error> {
+        let write = self.write || self.create || self.create_new;
+        let mode = match (self.read, write, self.append, self.truncate) {
+            (true, false, false, false) => "r",
+            (false, _, true, _) => "a",
+            (true, _, true, _) => "a+",
+            (false, true, false, true) => "w",
+            (true, true, false, true) => "w+",
+            (true, true, false, false) => "r+",
+            _ => return Err(Error::BadPath),
+        };
+        Ok(if self.binary {
+            format!("{}b", mode)
+        } else {
+            mode.to_string()
+        })
+    }
+
+    /// Resolves the configured flags to an fopen mode string and opens path with it.
+    /// # Errors
+    /// Returns Error::AlreadyExists if create_new(true) was set and the path already exists,
+    /// or any error CFile::open may return otherwise.
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<CFile, Error> {
+        if self.create_new {
+            let exists = if let Ok(cpath) = CString::new(path.as_ref().as_os_str().as_bytes()) {
+                unsafe {
+                    let mut stat: libc::stat = std::mem::zeroed();
+                    libc::stat(cpath.as_ptr(), &mut stat) == 0
+                }
+            } else {
+                return Err(Error::BadPath);
+            };
+            if exists {
+                return Err(Error::AlreadyExists);
+            }
+        }
+        // create/create_new imply write access (see mode_string), so read plus either of
+        // them without truncate also resolves to "r+".
+        if (self.create || self.create_new) && self.read && !self.truncate {
+            // fopen's "r+" (our read+write, no-truncate mode) requires the file to already
+            // exist, unlike create(true). Touch it first, mirroring the same
+            // touch-then-open pattern open_random_access/create_file already use.
+            let _ = CFile::create_file(&path);
+        }
+        let mode = self.mode_string()?;
+        CFile::open(path, &mode)
+    }
+}
+
+/// Converts a (seconds, nanoseconds) pair as reported by stat into a SystemTime. The
+/// callers always take the nanosecond component from libc::stat's st_*time_nsec fields;
+/// there is no fallback for platforms whose libc::stat lacks those fields.
+fn system_time(secs: i64, nsecs: i64) -> SystemTime {
+    if secs >= 0 {
+        UNIX_EPOCH + Duration::new(secs as u64, nsecs as u32)
+    } else {
+        UNIX_EPOCH - Duration::new((-secs) as u64, 0) + Duration::new(0, nsecs as u32)
+    }
+}
+
+/// The permissions of a file, as reported by the st_mode field of a stat call.
+pub struct Permissions(libc::mode_t);
+
+impl Permissions {
+    /// Returns the raw permission bits (as would be passed to chmod).
+    ///
+    /// mode_t is u32 on Linux but narrower on some other Unixes, so this cast isn't
+    /// always a no-op even though clippy flags it as one on this platform.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn mode(&self) -> u32 {
+        (self.0 as u32) & 0o7777
+    }
+
+    /// Returns true if none of the write bits are set.
+    pub fn readonly(&self) -> bool {
+        self.0 & 0o222 == 0
+    }
+}
+
+/// Metadata information about a file, populated via fstat. Mirrors the subset of
+/// std::fs::Metadata that fstat can provide.
+pub struct Metadata(libc::stat);
+
+impl Metadata {
+    /// Returns the size of the file, in bytes.
+    pub fn len(&self) -> u64 {
+        self.0.st_size as u64
+    }
+
+    /// Returns true if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if this file is a regular file.
+    pub fn is_file(&self) -> bool {
+        self.0.st_mode & libc::S_IFMT == libc::S_IFREG
+    }
+
+    /// Returns true if this file is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.0.st_mode & libc::S_IFMT == libc::S_IFDIR
+    }
+
+    /// Returns the permissions of the file.
+    pub fn permissions(&self) -> Permissions {
+        Permissions(self.0.st_mode)
+    }
+
+    /// Returns the last modification time of the file.
+    ///
+    /// st_mtime/st_mtime_nsec are already time_t/i64 on Linux, but the casts are kept
+    /// (and exempted here) since time_t is narrower on some other Unixes.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn modified(&self) -> SystemTime {
+        system_time(self.0.st_mtime as i64, self.0.st_mtime_nsec as i64)
+    }
+
+    /// Returns the last access time of the file.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn accessed(&self) -> SystemTime {
+        system_time(self.0.st_atime as i64, self.0.st_atime_nsec as i64)
+    }
+
+    /// Returns the inode change time (ctime) of the file, which stat reports in place of a
+    /// true creation time; this is not the time the file was created.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn created(&self) -> SystemTime {
+        system_time(self.0.st_ctime as i64, self.0.st_ctime_nsec as i64)
+    }
+
+    /// Returns the preferred block size for efficient I/O on this file.
+    pub fn blksize(&self) -> u64 {
+        self.0.st_blksize as u64
+    }
+
+    /// Returns the number of 512-byte blocks allocated to this file.
+    pub fn blocks(&self) -> u64 {
+        self.0.st_blocks as u64
+    }
+
+    /// Returns the last access time as seconds since the epoch. Paired with atime_nsec()
+    /// for the sub-second remainder, mirroring MetadataExt rather than modified()'s
+    /// SystemTime for callers that want the raw stat fields.
+    ///
+    /// These st_*time* fields are already time_t/i64 on Linux; the casts are exempted
+    /// from clippy's redundant-cast lint since time_t is narrower on some other Unixes.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn atime(&self) -> i64 {
+        self.0.st_atime as i64
+    }
+
+    /// Returns the nanosecond remainder of atime().
+    #[allow(clippy::unnecessary_cast)]
+    pub fn atime_nsec(&self) -> i64 {
+        self.0.st_atime_nsec as i64
+    }
+
+    /// Returns the last modification time as seconds since the epoch.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn mtime(&self) -> i64 {
+        self.0.st_mtime as i64
+    }
+
+    /// Returns the nanosecond remainder of mtime().
+    #[allow(clippy::unnecessary_cast)]
+    pub fn mtime_nsec(&self) -> i64 {
+        self.0.st_mtime_nsec as i64
+    }
+
+    /// Returns the last inode change time as seconds since the epoch.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn ctime(&self) -> i64 {
+        self.0.st_ctime as i64
+    }
+
+    /// Returns the nanosecond remainder of ctime().
+    #[allow(clippy::unnecessary_cast)]
+    pub fn ctime_nsec(&self) -> i64 {
+        self.0.st_ctime_nsec as i64
+    }
+}
+
 /// A wrapper around C's file type.
 /// Attempts to mimic the functionality if rust's std::fs::File while still allowing complete
 /// control of all I/O operations.
@@ -105,6 +368,19 @@ impl CFile {
         }
     }
 
+    /// Returns a builder that allows the open mode to be described declaratively via
+    /// `read`/`write`/`append`/`truncate`/`create`/`create_new`, rather than by passing a
+    /// raw fopen mode string.
+    /// # Examples
+    /// ```
+    /// use cfile_rs::CFile;
+    ///
+    /// let file = CFile::options().read(true).write(true).create(true).open("data.txt").unwrap();
+    /// ```
+    pub fn options() -> CFileOptions {
+        CFileOptions::new()
+    }
+
     /// Attempt to open the file with path p.
     /// # Examples
     /// ```
@@ -145,6 +421,35 @@ impl CFile {
         }
     }
 
+    /// Adopts an already-open file descriptor via fdopen, so descriptor-based APIs
+    /// (sockets, pipes, inherited fds) can be driven through the buffered CFile interface.
+    /// Since a bare descriptor has no associated path, `self.path` is set to an empty
+    /// string; callers relying on `delete()` or the path field should prefer `CFile::open`.
+    /// # Errors
+    /// On error Error::Errno(errno) is returned.
+    pub fn from_raw_fd(fd: RawFd, mode: &str) -> Result<CFile, Error> {
+        unsafe {
+            if let Ok(mode) = CString::new(mode) {
+                let file_ptr = libc::fdopen(fd, mode.as_ptr());
+                if file_ptr.is_null() {
+                    Err(get_error())
+                } else {
+                    Ok(CFile {
+                        file_ptr,
+                        path: CString::new(Vec::new()).unwrap(),
+                    })
+                }
+            } else {
+                Err(Error::BadPath)
+            }
+        }
+    }
+
+    /// Returns the raw file descriptor backing this CFile, via fileno.
+    pub fn as_raw_fd(&self) -> RawFd {
+        unsafe { libc::fileno(self.file_ptr) }
+    }
+
     /// Deletes the file from the filesystem, and consumes the object.
     /// # Errors
     /// On error Error::Errno(errno) is returned.
@@ -204,13 +509,86 @@ impl CFile {
     /// };
     /// ```
     pub fn write_all(&self, buf: &[u8]) -> Result<(), Error> {
+        let mut written = 0;
+        while written < buf.len() {
+            unsafe {
+                let n = libc::fwrite(
+                    buf[written..].as_ptr() as *const libc::c_void,
+                    1,
+                    buf.len() - written,
+                    self.file_ptr,
+                );
+                written += n;
+                if written == buf.len() {
+                    return Ok(());
+                }
+                if libc::ferror(self.file_ptr) != 0 {
+                    if *(libc::__errno_location()) == libc::EINTR {
+                        libc::clearerr(self.file_ptr);
+                        continue;
+                    }
+                    return Err(self.get_write_error(written));
+                }
+                return Err(self.get_write_error(written));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes from several buffers in a single writev(2) call, rather than one fwrite per
+    /// buffer. The stdio buffer is flushed first, since writev bypasses it and operates
+    /// directly on the underlying descriptor; the stream position is then resynced with
+    /// fseek so later stdio calls see a consistent offset.
+    /// # Errors
+    /// On error Error::Errno(errno) is returned.
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> Result<usize, Error> {
+        if bufs.is_empty() {
+            return Ok(0);
+        }
+        self.flush()?;
         unsafe {
-            let written_bytes = libc::fwrite(buf.as_ptr() as *const libc::c_void, 1, buf.len(), self.file_ptr);
-            if written_bytes != buf.len() {
-                Err(self.get_write_error(written_bytes))
-            } else {
-                Ok(())
+            let fd = libc::fileno(self.file_ptr);
+            if fd < 0 {
+                return Err(get_error());
             }
+            let result = libc::writev(fd, bufs.as_ptr() as *const libc::iovec, bufs.len() as libc::c_int);
+            if result < 0 {
+                return Err(get_error());
+            }
+            self.resync_position(fd);
+            Ok(result as usize)
+        }
+    }
+
+    /// Reads into several buffers in a single readv(2) call, rather than one fread per
+    /// buffer. The stdio buffer is flushed first for the same reason as write_vectored.
+    /// # Errors
+    /// On error Error::Errno(errno) is returned.
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> Result<usize, Error> {
+        if bufs.is_empty() {
+            return Ok(0);
+        }
+        self.flush()?;
+        unsafe {
+            let fd = libc::fileno(self.file_ptr);
+            if fd < 0 {
+                return Err(get_error());
+            }
+            let result = libc::readv(fd, bufs.as_ptr() as *const libc::iovec, bufs.len() as libc::c_int);
+            if result < 0 {
+                return Err(get_error());
+            }
+            self.resync_position(fd);
+            Ok(result as usize)
+        }
+    }
+
+    /// After a readv/writev on the raw fd, moves the stdio stream's notion of its position
+    /// to match the fd's actual offset.
+    unsafe fn resync_position(&self, fd: libc::c_int) {
+        let pos = libc::lseek(fd, 0, libc::SEEK_CUR);
+        if pos >= 0 {
+            libc::fseek(self.file_ptr, pos as libc::c_long, libc::SEEK_SET);
         }
     }
 
@@ -233,12 +611,16 @@ impl CFile {
     ///                         // stream will be written to the file
     /// ```
     pub fn flush(&self) -> Result<(), Error> {
-        unsafe {
-            let result = libc::fflush(self.file_ptr);
-            if result == 0 {
-                Ok(())
-            } else {
-                Err(get_error())
+        loop {
+            unsafe {
+                let result = libc::fflush(self.file_ptr);
+                if result == 0 {
+                    return Ok(());
+                }
+                if *(libc::__errno_location()) == libc::EINTR {
+                    continue;
+                }
+                return Err(get_error());
             }
         }
     }
@@ -354,17 +736,72 @@ impl CFile {
     /// };
     /// ```
     pub fn read_exact(&self, buf: &mut [u8]) -> Result<(), Error> {
-        unsafe {
-            let result = libc::fread(buf.as_ptr() as *mut libc::c_void, 1, buf.len(), self.file_ptr);
-            if result == buf.len() {
-                Ok(())
-            } else {
+        let mut read = 0;
+        while read < buf.len() {
+            unsafe {
+                let n = libc::fread(
+                    buf[read..].as_mut_ptr() as *mut libc::c_void,
+                    1,
+                    buf.len() - read,
+                    self.file_ptr,
+                );
+                read += n;
+                if read == buf.len() {
+                    return Ok(());
+                }
                 // Check if we hit the end of the file
                 if libc::feof(self.file_ptr) != 0 {
-                    Err(Error::EndOfFile(result as usize))
-                } else {
-                    Err(get_error())
+                    return Err(Error::EndOfFile(read));
+                }
+                if libc::ferror(self.file_ptr) != 0 {
+                    if *(libc::__errno_location()) == libc::EINTR {
+                        libc::clearerr(self.file_ptr);
+                        continue;
+                    }
+                    return Err(get_error());
                 }
+                return Err(get_error());
+            }
+        }
+        Ok(())
+    }
+
+    /// Queries metadata about the underlying file via fstat.
+    /// # Errors
+    /// On error Error::Errno(errno) is returned.
+    pub fn metadata(&self) -> Result<Metadata, Error> {
+        unsafe {
+            let fd = libc::fileno(self.file_ptr);
+            if fd < 0 {
+                return Err(get_error());
+            }
+            let mut stat: libc::stat = std::mem::zeroed();
+            if libc::fstat(fd, &mut stat) == 0 {
+                Ok(Metadata(stat))
+            } else {
+                Err(get_error())
+            }
+        }
+    }
+
+    /// Truncates or extends the underlying file to exactly `size` bytes. If the file is
+    /// extended, the new region is zero-filled; if shrunk, trailing bytes are discarded.
+    /// The stream is flushed first so buffered writes aren't lost, and the current stream
+    /// position is left untouched. Takes `&self`, like the rest of CFile's I/O methods,
+    /// since the mutation happens through the underlying FILE* rather than this handle.
+    /// # Errors
+    /// On error Error::Errno(errno) is returned.
+    pub fn set_len(&self, size: u64) -> Result<(), Error> {
+        self.flush()?;
+        unsafe {
+            let fd = libc::fileno(self.file_ptr);
+            if fd < 0 {
+                return Err(get_error());
+            }
+            if libc::ftruncate(fd, size as libc::off_t) == 0 {
+                Ok(())
+            } else {
+                Err(get_error())
             }
         }
     }
@@ -394,19 +831,23 @@ impl CFile {
     /// # Errors
     /// On error Error::Errno(errno) is returned.
     pub fn seek(&self, pos: SeekFrom) -> Result<u64, Error> {
-        unsafe {
-            let result = match pos {
-                SeekFrom::Start(from) =>
-                    libc::fseek(self.file_ptr, from as libc::c_long, libc::SEEK_SET),
-                SeekFrom::End(from) =>
-                    libc::fseek(self.file_ptr, from as libc::c_long, libc::SEEK_END),
-                SeekFrom::Current(delta) =>
-                    libc::fseek(self.file_ptr, delta as libc::c_long, libc::SEEK_CUR)
-            };
-            if result == 0 {
-                self.current_pos()
-            } else {
-                Err(get_error())
+        loop {
+            unsafe {
+                let result = match pos {
+                    SeekFrom::Start(from) =>
+                        libc::fseek(self.file_ptr, from as libc::c_long, libc::SEEK_SET),
+                    SeekFrom::End(from) =>
+                        libc::fseek(self.file_ptr, from as libc::c_long, libc::SEEK_END),
+                    SeekFrom::Current(delta) =>
+                        libc::fseek(self.file_ptr, delta as libc::c_long, libc::SEEK_CUR)
+                };
+                if result == 0 {
+                    return self.current_pos();
+                }
+                if *(libc::__errno_location()) == libc::EINTR {
+                    continue;
+                }
+                return Err(get_error());
             }
         }
     }
@@ -430,6 +871,61 @@ impl CFile {
     }
 }
 
+impl AsRawFd for CFile {
+    fn as_raw_fd(&self) -> RawFd {
+        CFile::as_raw_fd(self)
+    }
+}
+
+/// Lets a CFile be dropped into any pipeline built on std::io, e.g. wrapped in a
+/// BufReader/BufWriter or passed to io::copy, without disturbing the existing specialized
+/// API (read_exact/write_all/seek/flush) that returns the crate's own Error type.
+impl io::Read for CFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        unsafe {
+            let n = libc::fread(buf.as_mut_ptr() as *mut libc::c_void, 1, buf.len(), self.file_ptr);
+            if n < buf.len() && libc::ferror(self.file_ptr) != 0 {
+                Err(io::Error::from_raw_os_error(*(libc::__errno_location())))
+            } else {
+                // A short read with feof set (rather than ferror) isn't an error -- it's
+                // just the end of the file, so we report however many bytes were read.
+                Ok(n)
+            }
+        }
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        CFile::read_vectored(self, bufs).map_err(|err| io::Error::from_raw_os_error(err.errno() as i32))
+    }
+}
+
+impl io::Write for CFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        unsafe {
+            let n = libc::fwrite(buf.as_ptr() as *const libc::c_void, 1, buf.len(), self.file_ptr);
+            if n < buf.len() && libc::ferror(self.file_ptr) != 0 {
+                Err(io::Error::from_raw_os_error(*(libc::__errno_location())))
+            } else {
+                Ok(n)
+            }
+        }
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        CFile::write_vectored(self, bufs).map_err(|err| io::Error::from_raw_os_error(err.errno() as i32))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        CFile::flush(self).map_err(|err| io::Error::from_raw_os_error(err.errno() as i32))
+    }
+}
+
+impl io::Seek for CFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        CFile::seek(self, pos).map_err(|err| io::Error::from_raw_os_error(err.errno() as i32))
+    }
+}
+
 impl Drop for CFile {
     /// Ensures the file stream is closed before abandoning the data.
     fn drop(&mut self) {
@@ -448,3 +944,251 @@ impl Drop for CFile {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{buffer, CFile, TRUNCATAE_RANDOM_ACCESS_MODE};
+    use crate::error::Error;
+    use std::io::{self, SeekFrom};
+    use std::path::Path;
+    use std::str;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn options_create_new_rejects_existing_path() {
+        let path = "cfile_test_options_create_new.txt";
+        let _ = std::fs::remove_file(path);
+        CFile::create_file(&path).unwrap();
+
+        match CFile::options().write(true).create_new(true).open(path) {
+            Err(Error::AlreadyExists) => {}
+            Ok(_) => panic!("expected AlreadyExists, got Ok"),
+            Err(e) => panic!("expected AlreadyExists, got {:?}", e),
+        }
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn options_truncate_without_write_is_bad_path() {
+        match CFile::options().read(true).truncate(true).open("cfile_test_options_bad_path.txt") {
+            Err(Error::BadPath) => {}
+            Ok(_) => panic!("expected BadPath, got Ok"),
+            Err(e) => panic!("expected BadPath, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn options_write_only_no_truncate_is_rejected_not_destructive() {
+        let path = "cfile_test_options_write_only_no_truncate.txt";
+        let _ = std::fs::remove_file(path);
+        CFile::open(path, TRUNCATAE_RANDOM_ACCESS_MODE)
+            .unwrap()
+            .write_all(b"Howdy folks!")
+            .unwrap();
+
+        match CFile::options().write(true).truncate(false).open(path) {
+            Err(Error::BadPath) => {}
+            Ok(_) => panic!("expected BadPath, got Ok"),
+            Err(e) => panic!("expected BadPath, got {:?}", e),
+        }
+
+        // fopen has no non-destructive write-only mode, so the rejected combination
+        // above must never have gotten far enough to open (and truncate) the file.
+        let file = CFile::open(path, "r").unwrap();
+        let mut buf = buffer(12);
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"Howdy folks!");
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn options_create_read_write_no_truncate_touches_then_opens() {
+        let path = "cfile_test_options_touch_then_open.txt";
+        let _ = std::fs::remove_file(path);
+
+        let file = CFile::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path)
+            .unwrap();
+        file.write_all(b"Howdy folks!").unwrap();
+        let _ = file.seek(SeekFrom::Start(0));
+        let mut buf = buffer(12);
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"Howdy folks!");
+
+        let _ = file.close();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn options_create_alone_is_bad_path() {
+        // create(true) implies write access, but with neither read nor truncate set
+        // this is the same non-truncating write-only combination mode_string rejects.
+        let path = "cfile_test_options_create_alone.txt";
+        let _ = std::fs::remove_file(path);
+
+        match CFile::options().create(true).open(path) {
+            Err(Error::BadPath) => {}
+            Ok(_) => panic!("expected BadPath, got Ok"),
+            Err(e) => panic!("expected BadPath, got {:?}", e),
+        }
+        assert!(!Path::new(path).exists());
+    }
+
+    #[test]
+    fn io_copy_drives_read_and_write_impls() {
+        use std::io::{BufReader, Read};
+
+        let src_path = "cfile_test_io_copy_src.txt";
+        let dst_path = "cfile_test_io_copy_dst.txt";
+        let src = CFile::open(src_path, TRUNCATAE_RANDOM_ACCESS_MODE).unwrap();
+        src.write_all(b"Howdy folks!").unwrap();
+        let _ = src.seek(SeekFrom::Start(0));
+
+        let mut dst = CFile::open(dst_path, TRUNCATAE_RANDOM_ACCESS_MODE).unwrap();
+        let mut reader = BufReader::new(src);
+        let copied = io::copy(&mut reader, &mut dst).unwrap();
+        assert_eq!(copied, 12);
+
+        dst.flush().unwrap();
+        let _ = dst.seek(SeekFrom::Start(0));
+        let mut out = String::new();
+        dst.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "Howdy folks!");
+
+        let _ = std::fs::remove_file(src_path);
+        let _ = std::fs::remove_file(dst_path);
+    }
+
+    #[test]
+    fn from_raw_fd_round_trips_through_as_raw_fd() {
+        use std::os::unix::io::AsRawFd;
+
+        let path = "cfile_test_raw_fd.txt";
+        let file = CFile::open(path, TRUNCATAE_RANDOM_ACCESS_MODE).unwrap();
+        file.write_all(b"Howdy folks!").unwrap();
+        file.flush().unwrap();
+        let _ = file.seek(SeekFrom::Start(0));
+
+        let fd = file.as_raw_fd();
+        assert_eq!(AsRawFd::as_raw_fd(&file), fd);
+
+        let adopted = CFile::from_raw_fd(fd, "r").unwrap();
+        assert!(adopted.path.as_bytes().is_empty());
+
+        let mut buf = buffer(12);
+        adopted.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"Howdy folks!");
+
+        let _ = adopted.close();
+        // `adopted` already closed the shared fd, so avoid a double-close via file's Drop.
+        std::mem::forget(file);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn metadata_reports_size_type_and_times() {
+        let path = "cfile_test_metadata.txt";
+        let file = CFile::open(path, TRUNCATAE_RANDOM_ACCESS_MODE).unwrap();
+        file.write_all(b"Howdy folks!").unwrap();
+        file.flush().unwrap();
+
+        let meta = file.metadata().unwrap();
+        assert_eq!(meta.len(), 12);
+        assert!(!meta.is_empty());
+        assert!(meta.is_file());
+        assert!(!meta.is_dir());
+        assert!(!meta.permissions().readonly());
+        assert_eq!(meta.mtime(), meta.modified().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64);
+        assert_eq!(meta.atime(), meta.accessed().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64);
+        assert_eq!(meta.ctime(), meta.created().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64);
+        assert!(meta.mtime_nsec() >= 0);
+        assert!(meta.atime_nsec() >= 0);
+        assert!(meta.ctime_nsec() >= 0);
+
+        let _ = file.close();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn set_len_grows_and_shrinks() {
+        let path = "cfile_test_set_len.txt";
+        let file = CFile::open(path, TRUNCATAE_RANDOM_ACCESS_MODE).unwrap();
+        file.write_all(b"Howdy folks!").unwrap();
+        let pos = file.current_pos().unwrap();
+
+        file.set_len(20).unwrap();
+        assert_eq!(file.metadata().unwrap().len(), 20);
+        assert_eq!(file.current_pos().unwrap(), pos);
+
+        file.set_len(4).unwrap();
+        assert_eq!(file.metadata().unwrap().len(), 4);
+        assert_eq!(file.current_pos().unwrap(), pos);
+
+        let _ = file.close();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn vectored_read_write_round_trip() {
+        use std::io::{IoSlice, IoSliceMut};
+
+        let path = "cfile_test_vectored.txt";
+        let file = CFile::open(path, TRUNCATAE_RANDOM_ACCESS_MODE).unwrap();
+        let bufs = [IoSlice::new(b"Howdy "), IoSlice::new(b"folks!")];
+        let written = file.write_vectored(&bufs).unwrap();
+        assert_eq!(written, 12);
+
+        let _ = file.seek(SeekFrom::Start(0));
+        let mut first = [0u8; 6];
+        let mut second = [0u8; 6];
+        let mut bufs = [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)];
+        let read = file.read_vectored(&mut bufs).unwrap();
+        assert_eq!(read, 12);
+        assert_eq!(&first, b"Howdy ");
+        assert_eq!(&second, b"folks!");
+
+        // The fd-level readv should have resynced the stdio position, so a
+        // subsequent buffered read sees end-of-file rather than rereading.
+        let mut rest = buffer(4);
+        match file.read_exact(&mut rest) {
+            Err(Error::EndOfFile(0)) => {}
+            other => panic!("expected EndOfFile(0), got {:?}", other),
+        }
+
+        let _ = file.close();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn file_flush() {
+        let file = CFile::open("data.txt", TRUNCATAE_RANDOM_ACCESS_MODE).unwrap();
+        match file.write_all("Howdy folks!".as_bytes()) {
+            Ok(()) => println!("Successfully wrote to the file!"),
+            Err(_e) => {
+                // darn
+            }
+        };
+        let _ = file.flush(); // Probably unnecessary
+        let buf_size = 20;
+        let mut buf = buffer(buf_size); // 20 will be more than enough to store our data
+        let _ = file.seek(SeekFrom::Start(0)); // Move back to the beginning of the file
+        let result = file.read_exact(&mut buf); // Read exactly 20 bytes
+        match result {
+            Ok(()) => {
+                // This won't happen since we only wrote 12 bytes,
+                let data = &buf[0..buf_size]; // but if it did this is how we could print the data
+                                              // as a string.
+                let str = str::from_utf8(data).unwrap();
+                println!("{}", str);
+            }
+            Err(_e) => {
+                // Oh no!
+            }
+        };
+    }
+}
@@ -0,0 +1,205 @@
+/*
+MIT License
+
+Copyright (c) 2017 Joshua Karns
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+associated documentation files (the "Software"), to deal in the Software without restriction,
+including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so,
+subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE
+SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+use libc;
+
+use crate::error::Error;
+
+use std::ffi::{CStr, CString, OsString};
+use std::path::{Path, PathBuf};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+/// A utility function to pull the current value of errno and put it into an Error::Errno
+unsafe fn get_error() -> Error {
+    Error::Errno(*(libc::__errno_location()) as u64)
+}
+
+/// Wraps the raw `*mut libc::DIR` so ReadDir can be handed across threads. This is sound
+/// because every operation we perform on it (readdir/closedir) is done through &mut access.
+struct Dir(*mut libc::DIR);
+
+unsafe impl Send for Dir {}
+unsafe impl Sync for Dir {}
+
+impl Drop for Dir {
+    fn drop(&mut self) {
+        unsafe {
+            libc::closedir(self.0);
+        }
+    }
+}
+
+/// The type of a directory entry, derived from d_type when the platform provides it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FileType(u8);
+
+impl FileType {
+    pub fn is_dir(&self) -> bool {
+        self.0 == libc::DT_DIR
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.0 == libc::DT_REG
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.0 == libc::DT_LNK
+    }
+}
+
+/// An entry within a directory, as yielded by ReadDir.
+pub struct DirEntry {
+    name: CString,
+    root: PathBuf,
+    file_type: u8,
+}
+
+impl DirEntry {
+    /// The bare file name of this entry, without the directory it was found in.
+    pub fn file_name(&self) -> OsString {
+        OsString::from_vec(self.name.as_bytes().to_vec())
+    }
+
+    /// The full path of this entry, formed by joining the directory root with file_name().
+    pub fn path(&self) -> PathBuf {
+        self.root.join(self.file_name())
+    }
+
+    /// The type of this entry. If the filesystem didn't report it via d_type (DT_UNKNOWN),
+    /// this falls back to an lstat of the entry's path.
+    pub fn file_type(&self) -> Result<FileType, Error> {
+        if self.file_type != libc::DT_UNKNOWN {
+            return Ok(FileType(self.file_type));
+        }
+        unsafe {
+            let cpath = CString::new(self.path().as_os_str().as_bytes()).map_err(|_| Error::BadPath)?;
+            let mut stat: libc::stat = std::mem::zeroed();
+            if libc::lstat(cpath.as_ptr(), &mut stat) == 0 {
+                let d_type = match stat.st_mode & libc::S_IFMT {
+                    libc::S_IFDIR => libc::DT_DIR,
+                    libc::S_IFREG => libc::DT_REG,
+                    libc::S_IFLNK => libc::DT_LNK,
+                    _ => libc::DT_UNKNOWN,
+                };
+                Ok(FileType(d_type))
+            } else {
+                Err(get_error())
+            }
+        }
+    }
+}
+
+/// An iterator over the entries of a directory, built on opendir/readdir. The underlying
+/// `DIR*` handle is closed via closedir when this is dropped.
+pub struct ReadDir {
+    dir: Dir,
+    root: PathBuf,
+}
+
+impl Iterator for ReadDir {
+    type Item = Result<DirEntry, Error>;
+
+    fn next(&mut self) -> Option<Result<DirEntry, Error>> {
+        loop {
+            unsafe {
+                *(libc::__errno_location()) = 0;
+                let entry = libc::readdir(self.dir.0);
+                if entry.is_null() {
+                    if *(libc::__errno_location()) != 0 {
+                        return Some(Err(get_error()));
+                    }
+                    return None;
+                }
+                let name = CStr::from_ptr((*entry).d_name.as_ptr()).to_owned();
+                if name.as_bytes() == b"." || name.as_bytes() == b".." {
+                    continue;
+                }
+                return Some(Ok(DirEntry {
+                    name,
+                    root: self.root.clone(),
+                    file_type: (*entry).d_type,
+                }));
+            }
+        }
+    }
+}
+
+/// Opens `path` as a directory and returns an iterator over its entries (skipping `.`
+/// and `..`).
+pub fn read_dir<P: AsRef<Path>>(path: P) -> Result<ReadDir, Error> {
+    unsafe {
+        let cpath = CString::new(path.as_ref().as_os_str().as_bytes()).map_err(|_| Error::BadPath)?;
+        let dirp = libc::opendir(cpath.as_ptr());
+        if dirp.is_null() {
+            Err(get_error())
+        } else {
+            Ok(ReadDir {
+                dir: Dir(dirp),
+                root: path.as_ref().to_path_buf(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_dir;
+    use std::collections::HashSet;
+    use std::fs;
+
+    #[test]
+    fn read_dir_yields_file_and_subdir() {
+        let root = std::env::temp_dir().join("cfile_test_read_dir");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("a_subdir")).unwrap();
+        fs::write(root.join("a_file.txt"), b"hello").unwrap();
+
+        let names: HashSet<_> = read_dir(&root)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(std::ffi::OsStr::new("a_subdir")));
+        assert!(names.contains(std::ffi::OsStr::new("a_file.txt")));
+
+        for entry in read_dir(&root).unwrap() {
+            let entry = entry.unwrap();
+            assert_eq!(entry.path(), root.join(entry.file_name()));
+            if entry.file_name() == "a_subdir" {
+                assert!(entry.file_type().unwrap().is_dir());
+            } else {
+                assert!(entry.file_type().unwrap().is_file());
+            }
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn read_dir_skips_dot_and_dotdot() {
+        let root = std::env::temp_dir().join("cfile_test_read_dir_dots");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        let count = read_dir(&root).unwrap().count();
+        assert_eq!(count, 0);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -34,7 +34,9 @@ pub enum Error {
     /// bytes_written
     EndOfFile(usize),
     /// bytes written, errno
-    WriteError(usize, u64)
+    WriteError(usize, u64),
+    /// Returned by CFile::options().create_new(true).open(..) when the path already exists.
+    AlreadyExists
 }
 
 impl Error {
@@ -57,6 +59,9 @@ impl Error {
                 },
                 &Error::EndOfFile(_) => {
                     CStr::from_ptr("The end of the file was reached\0".as_ptr() as *const i8)
+                },
+                &Error::AlreadyExists => {
+                    CStr::from_ptr("The path supplied already exists\0".as_ptr() as *const i8)
                 }
             }
         }